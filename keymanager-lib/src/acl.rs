@@ -0,0 +1,109 @@
+//! Per-runtime, per-key-id access control, sourced from on-chain state and the policy document.
+use std::sync::RwLock;
+
+use anyhow::Result;
+use io_context::Context as IoContext;
+use lazy_static::lazy_static;
+
+use oasis_core_keymanager_api_common::AclRule;
+use oasis_core_runtime::{
+    common::namespace::Namespace,
+    consensus::state::{registry::ImmutableState as RegistryState, ConsensusState},
+};
+
+fn rule_covers(rule: &AclRule, runtime_id: &Namespace, key_id: &[u8]) -> bool {
+    rule.runtime_id == *runtime_id && key_id.starts_with(&rule.key_id_prefix)
+}
+
+lazy_static! {
+    static ref ACL_STORE: AclStore = AclStore::new();
+}
+
+struct Cache {
+    /// Consensus height the cached rules were loaded at; `None` before the first load.
+    height: Option<u64>,
+    rules: Vec<AclRule>,
+}
+
+/// Caches the on-chain ACL table (one set of rules per consensus height) and answers
+/// allow/deny questions against it, combined with whatever rules the policy document itself
+/// declares.
+pub struct AclStore {
+    cache: RwLock<Cache>,
+}
+
+impl AclStore {
+    fn new() -> Self {
+        Self {
+            cache: RwLock::new(Cache {
+                height: None,
+                rules: Vec::new(),
+            }),
+        }
+    }
+
+    /// Return the global ACL store instance.
+    pub fn global<'a>() -> &'a AclStore {
+        &ACL_STORE
+    }
+
+    /// Whether `runtime_id` may request `key_id`, per the on-chain ACL table at `height` together
+    /// with any rules declared in the policy document's ACL section.
+    ///
+    /// If neither source declares any rule at all, nothing has opted this key manager into
+    /// per-runtime scoping, so access is allowed -- preserving the pre-ACL behavior of trusting
+    /// any attested runtime, rather than bricking `get_or_create_keys` for everyone the moment
+    /// this feature ships into a deployment that hasn't configured it yet.
+    pub fn is_allowed(
+        &self,
+        state: &ConsensusState,
+        height: u64,
+        runtime_id: &Namespace,
+        key_id: &[u8],
+        policy_rules: &[AclRule],
+    ) -> Result<bool> {
+        if policy_rules.iter().any(|r| rule_covers(r, runtime_id, key_id)) {
+            return Ok(true);
+        }
+
+        self.refresh(state, height)?;
+        let cache = self.cache.read().unwrap();
+        if policy_rules.is_empty() && cache.rules.is_empty() {
+            return Ok(true);
+        }
+        Ok(cache.rules.iter().any(|r| rule_covers(r, runtime_id, key_id)))
+    }
+
+    /// Reload the on-chain ACL table if the cache is stale relative to `height`.
+    fn refresh(&self, state: &ConsensusState, height: u64) -> Result<()> {
+        {
+            let cache = self.cache.read().unwrap();
+            if cache.height == Some(height) {
+                return Ok(());
+            }
+        }
+
+        let registry_state = RegistryState::new(state);
+        let rules = registry_state.key_manager_acl(IoContext::background())?;
+
+        let mut cache = self.cache.write().unwrap();
+        cache.height = Some(height);
+        cache.rules = rules;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_covers_matches_prefix_within_runtime() {
+        let rule = AclRule {
+            runtime_id: Namespace::default(),
+            key_id_prefix: b"session/".to_vec(),
+        };
+        assert!(rule_covers(&rule, &Namespace::default(), b"session/abc"));
+        assert!(!rule_covers(&rule, &Namespace::default(), b"other/abc"));
+    }
+}
@@ -0,0 +1,75 @@
+//! Calls to peer key-manager enclaves over EnclaveRPC.
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+
+use oasis_core_runtime::{
+    common::crypto::signature::PublicKey, enclave_rpc::client::RpcClient, protocol::Protocol,
+};
+
+use crate::dkg;
+
+/// A thin client for calling another key-manager enclave's RPC methods over the authenticated
+/// EnclaveRPC transport.
+pub trait PeerClient: Send + Sync {
+    /// Fetch `peer`'s evaluation of its own polynomial at `recipient_index`, and the commitment
+    /// needed to verify it, for the given DKG session.
+    fn get_master_secret_share(
+        &self,
+        peer: &PublicKey,
+        session_id: dkg::SessionId,
+        recipient_index: dkg::ParticipantIndex,
+    ) -> Result<(dkg::Commitment, dkg::Share)>;
+}
+
+/// `PeerClient` backed by a real EnclaveRPC session to the peer node.
+pub struct EnclaveRpcPeerClient {
+    protocol: Arc<Protocol>,
+}
+
+impl EnclaveRpcPeerClient {
+    pub fn new(protocol: Arc<Protocol>) -> Self {
+        Self { protocol }
+    }
+}
+
+impl PeerClient for EnclaveRpcPeerClient {
+    fn get_master_secret_share(
+        &self,
+        peer: &PublicKey,
+        session_id: dkg::SessionId,
+        recipient_index: dkg::ParticipantIndex,
+    ) -> Result<(dkg::Commitment, dkg::Share)> {
+        let rpc = RpcClient::new_session(self.protocol.clone(), *peer);
+        let resp: ShareResponse = rpc
+            .call_secure(
+                oasis_core_keymanager_api_common::METHOD_GET_MASTER_SECRET_SHARE,
+                ShareRequest {
+                    session_id,
+                    recipient_index,
+                },
+            )
+            .map_err(|err| anyhow!("failed to call peer {}: {}", peer, err))?;
+
+        let commitment = dkg::Commitment::from_bytes(&resp.commitment)?;
+        let share = dkg::Share::from_bytes(&resp.share)?;
+        Ok((commitment, share))
+    }
+}
+
+/// Request body for `METHOD_GET_MASTER_SECRET_SHARE`: asks the peer for its own polynomial's
+/// evaluation at `recipient_index` -- the raw, singly-committed share the requester needs to
+/// verify-and-accumulate, not anything the peer has itself already combined from others.
+#[derive(Clone, Debug, cbor::Encode, cbor::Decode)]
+pub struct ShareRequest {
+    pub session_id: dkg::SessionId,
+    pub recipient_index: dkg::ParticipantIndex,
+}
+
+/// Response body for `METHOD_GET_MASTER_SECRET_SHARE`. `commitment`/`share` are the wire (byte)
+/// encodings of `dkg::Commitment`/`dkg::Share`, which don't themselves implement `cbor::Encode`.
+#[derive(Clone, Debug, cbor::Encode, cbor::Decode)]
+pub struct ShareResponse {
+    pub commitment: Vec<u8>,
+    pub share: [u8; 64],
+}
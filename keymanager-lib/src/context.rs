@@ -0,0 +1,16 @@
+use std::sync::Arc;
+
+use oasis_core_runtime::{
+    common::namespace::Namespace, consensus::verifier::Verifier, protocol::Protocol,
+};
+
+/// Per-request RPC context, installed by `new_keymanager`'s context initializer.
+pub struct Context {
+    /// Runtime ID of the key manager runtime itself (used to derive the DKG session id).
+    pub runtime_id: Namespace,
+    /// Handle to the runtime host protocol, used to reach consensus layer state and to dial
+    /// peer key-manager enclaves over EnclaveRPC.
+    pub protocol: Arc<Protocol>,
+    /// Consensus layer verifier, used to fetch the latest verified state for ACL checks.
+    pub consensus_verifier: Arc<dyn Verifier>,
+}
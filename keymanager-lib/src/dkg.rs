@@ -0,0 +1,310 @@
+//! Pedersen verifiable secret sharing (VSS) for distributed master secret generation.
+//!
+//! Each of the `n` participating key-manager enclaves samples its own random polynomial of
+//! degree `t-1`, commits to its coefficients with a Pedersen commitment (so peers can verify the
+//! share they receive without learning the polynomial), and evaluates it at every
+//! participant's index, its own included. The joint master secret is the sum of every participant's constant term;
+//! by linearity the sum of the individual shares at any index `x` is itself a valid share of that
+//! joint secret, so no single enclave ever materializes it until `t` combined shares are
+//! Lagrange-interpolated at `x = 0`.
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use curve25519_dalek::{
+    constants::RISTRETTO_BASEPOINT_POINT, ristretto::RistrettoPoint, scalar::Scalar,
+};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha512};
+
+/// Identifier for a single DKG round, shared by all participating enclaves.
+pub type SessionId = [u8; 32];
+
+/// A participant's index in the DKG. Indices start at 1; `x = 0` is the (never directly
+/// evaluated) point that holds the secret.
+pub type ParticipantIndex = u32;
+
+/// Second, nothing-up-my-sleeve generator used for the Pedersen commitment's blinding term.
+fn pedersen_h() -> RistrettoPoint {
+    let mut hasher = Sha512::new();
+    hasher.update(b"oasis-core/keymanager-lib: dkg pedersen h");
+    RistrettoPoint::from_uniform_bytes(&hasher.finalize().into())
+}
+
+/// A single evaluated share of a participant's polynomial, sent to one specific peer.
+#[derive(Clone, Copy, Debug)]
+pub struct Share {
+    pub value: Scalar,
+    pub blinding: Scalar,
+}
+
+impl Share {
+    fn zero() -> Self {
+        Share {
+            value: Scalar::ZERO,
+            blinding: Scalar::ZERO,
+        }
+    }
+
+    fn add(&self, other: &Share) -> Share {
+        Share {
+            value: self.value + other.value,
+            blinding: self.blinding + other.blinding,
+        }
+    }
+
+    /// Wire representation: two little-endian scalars, for carrying a share over EnclaveRPC.
+    pub fn to_bytes(self) -> [u8; 64] {
+        let mut buf = [0u8; 64];
+        buf[..32].copy_from_slice(self.value.as_bytes());
+        buf[32..].copy_from_slice(self.blinding.as_bytes());
+        buf
+    }
+
+    pub fn from_bytes(bytes: &[u8; 64]) -> Result<Self> {
+        let value = Scalar::from_canonical_bytes(bytes[..32].try_into().unwrap())
+            .into_option()
+            .ok_or_else(|| anyhow!("dkg: malformed share value"))?;
+        let blinding = Scalar::from_canonical_bytes(bytes[32..].try_into().unwrap())
+            .into_option()
+            .ok_or_else(|| anyhow!("dkg: malformed share blinding"))?;
+        Ok(Share { value, blinding })
+    }
+}
+
+/// Pedersen commitments to the coefficients of a participant's polynomial (lowest degree first),
+/// used by a peer to verify a `Share` it received without trusting the sender.
+#[derive(Clone, Debug)]
+pub struct Commitment(pub Vec<RistrettoPoint>);
+
+impl Commitment {
+    /// Verify that `share` is the evaluation at `index` of the polynomial committed to here.
+    pub fn verify(&self, index: ParticipantIndex, share: &Share) -> bool {
+        let x = Scalar::from(index as u64);
+        let mut rhs = RistrettoPoint::default();
+        let mut x_pow = Scalar::ONE;
+        for c in &self.0 {
+            rhs += c * x_pow;
+            x_pow *= x;
+        }
+        let lhs = RISTRETTO_BASEPOINT_POINT * share.value + pedersen_h() * share.blinding;
+        lhs == rhs
+    }
+
+    /// Wire representation: one compressed Ristretto point per coefficient.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0.iter().flat_map(|p| p.compress().to_bytes()).collect()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() % 32 != 0 {
+            return Err(anyhow!("dkg: malformed commitment"));
+        }
+        let points = bytes
+            .chunks_exact(32)
+            .map(|chunk| {
+                curve25519_dalek::ristretto::CompressedRistretto::from_slice(chunk)
+                    .map_err(|_| anyhow!("dkg: malformed commitment point"))?
+                    .decompress()
+                    .ok_or_else(|| anyhow!("dkg: invalid commitment point"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Commitment(points))
+    }
+}
+
+/// One participant's contribution to a DKG session.
+#[derive(Clone)]
+pub struct Contribution {
+    /// Commitment to this participant's degree `t-1` polynomial.
+    pub commitment: Commitment,
+    /// This participant's evaluation of its polynomial at every participant's index (its own
+    /// included), keyed by the recipient's index.
+    pub shares: BTreeMap<ParticipantIndex, Share>,
+}
+
+/// Sample a fresh random polynomial of degree `threshold - 1` (plus a matching blinding
+/// polynomial for the Pedersen commitment) and evaluate both at each index in `participants`.
+pub fn contribute(threshold: u32, participants: &[ParticipantIndex]) -> Contribution {
+    assert!(threshold >= 1, "threshold must be at least 1");
+
+    let mut rng = OsRng;
+    let coeffs: Vec<Scalar> = (0..threshold).map(|_| Scalar::random(&mut rng)).collect();
+    let blind_coeffs: Vec<Scalar> = (0..threshold).map(|_| Scalar::random(&mut rng)).collect();
+
+    let commitment = Commitment(
+        coeffs
+            .iter()
+            .zip(blind_coeffs.iter())
+            .map(|(c, b)| RISTRETTO_BASEPOINT_POINT * c + pedersen_h() * b)
+            .collect(),
+    );
+
+    let shares = participants
+        .iter()
+        .map(|&index| {
+            let x = Scalar::from(index as u64);
+            (
+                index,
+                Share {
+                    value: eval_poly(&coeffs, x),
+                    blinding: eval_poly(&blind_coeffs, x),
+                },
+            )
+        })
+        .collect();
+
+    Contribution { commitment, shares }
+}
+
+fn eval_poly(coeffs: &[Scalar], x: Scalar) -> Scalar {
+    let mut acc = Scalar::ZERO;
+    let mut x_pow = Scalar::ONE;
+    for c in coeffs {
+        acc += c * x_pow;
+        x_pow *= x;
+    }
+    acc
+}
+
+/// Accumulates verified per-participant shares received at a single index `own_index`, folding
+/// them into a running combined share. Only the sum and a count are kept, not the individual
+/// shares or their commitments.
+pub struct Accumulator {
+    own_index: ParticipantIndex,
+    combined: Share,
+    received_from: usize,
+}
+
+impl Accumulator {
+    pub fn new(own_index: ParticipantIndex) -> Self {
+        Accumulator {
+            own_index,
+            combined: Share::zero(),
+            received_from: 0,
+        }
+    }
+
+    /// Verify `share` against `commitment` for our own index and, if valid, fold it into the
+    /// running combined share. Returns an error (and does not mutate state) on a bad share.
+    pub fn accept(&mut self, commitment: &Commitment, share: &Share) -> Result<()> {
+        if !commitment.verify(self.own_index, share) {
+            return Err(anyhow!(
+                "dkg: share for participant {} failed commitment verification",
+                self.own_index
+            ));
+        }
+        self.combined = self.combined.add(share);
+        self.received_from += 1;
+        Ok(())
+    }
+
+    /// The combined share accumulated so far: a point on the joint polynomial at `own_index`.
+    pub fn combined_share(&self) -> Share {
+        self.combined
+    }
+
+    pub fn contributions_received(&self) -> usize {
+        self.received_from
+    }
+}
+
+/// Combine at least `threshold` distinct participants' combined shares via Lagrange
+/// interpolation at `x = 0` to recover the joint secret.
+pub fn recover(shares: &BTreeMap<ParticipantIndex, Share>, threshold: u32) -> Result<Scalar> {
+    if shares.len() < threshold as usize {
+        return Err(anyhow!(
+            "dkg: insufficient shares for recovery: have {}, need {}",
+            shares.len(),
+            threshold
+        ));
+    }
+
+    let indices: Vec<ParticipantIndex> = shares.keys().take(threshold as usize).cloned().collect();
+    let mut secret = Scalar::ZERO;
+    for &i in &indices {
+        let xi = Scalar::from(i as u64);
+        let mut num = Scalar::ONE;
+        let mut den = Scalar::ONE;
+        for &j in &indices {
+            if i == j {
+                continue;
+            }
+            let xj = Scalar::from(j as u64);
+            num *= xj;
+            den *= xj - xi;
+        }
+        secret += shares[&i].value * (num * den.invert());
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run a full `t`-of-`n` DKG round in-process: every participant contributes, every
+    /// participant accumulates every other's share, and `threshold` of the resulting combined
+    /// shares are enough to recover the joint secret.
+    fn run_round(n: u32, t: u32) -> (Scalar, BTreeMap<ParticipantIndex, Share>) {
+        let participants: Vec<ParticipantIndex> = (1..=n).collect();
+        let contributions: Vec<Contribution> = (0..n).map(|_| contribute(t, &participants)).collect();
+
+        // Each contribution's own shares, interpolated at 0, reveal that contribution's constant
+        // term; the joint secret is their sum. This is only possible here because the test has
+        // every share for every contribution, unlike a real enclave.
+        let expected_secret: Scalar = contributions
+            .iter()
+            .map(|c| recover(&c.shares.clone().into_iter().collect(), t).unwrap())
+            .fold(Scalar::ZERO, |acc, s| acc + s);
+
+        let mut accumulators: BTreeMap<ParticipantIndex, Accumulator> = participants
+            .iter()
+            .map(|&i| (i, Accumulator::new(i)))
+            .collect();
+
+        for contribution in &contributions {
+            for (&index, share) in &contribution.shares {
+                accumulators
+                    .get_mut(&index)
+                    .unwrap()
+                    .accept(&contribution.commitment, share)
+                    .unwrap();
+            }
+        }
+
+        let combined: BTreeMap<ParticipantIndex, Share> = accumulators
+            .iter()
+            .map(|(&i, acc)| (i, acc.combined_share()))
+            .collect();
+
+        (expected_secret, combined)
+    }
+
+    #[test]
+    fn test_share_verification_rejects_tampered_share() {
+        let participants = vec![1, 2, 3];
+        let contribution = contribute(2, &participants);
+        let mut bad_share = contribution.shares[&1];
+        bad_share.value += Scalar::ONE;
+
+        let mut acc = Accumulator::new(1);
+        assert!(acc.accept(&contribution.commitment, &bad_share).is_err());
+        assert_eq!(acc.contributions_received(), 0);
+    }
+
+    #[test]
+    fn test_recovery_rejects_fewer_than_threshold_shares() {
+        let (_, combined) = run_round(5, 3);
+        let partial: BTreeMap<ParticipantIndex, Share> =
+            combined.into_iter().take(2).collect();
+        assert!(recover(&partial, 3).is_err());
+    }
+
+    #[test]
+    fn test_full_threshold_round_trip() {
+        let (expected_secret, combined) = run_round(5, 3);
+        let subset: BTreeMap<ParticipantIndex, Share> = combined.into_iter().take(3).collect();
+        let recovered = recover(&subset, 3).unwrap();
+        assert_eq!(recovered, expected_secret);
+    }
+}
@@ -0,0 +1,216 @@
+//! Derivation of epoch-scoped ephemeral secrets.
+//!
+//! Each epoch's secret is keyed independently as `H(master_secret, epoch)`, so every key-manager
+//! replica derives the identical secret for a given epoch regardless of which epoch it happens to
+//! be asked for first or how many times it has restarted -- there is no per-instance chain state
+//! for that to depend on. Retaining only the last `N` epochs and zeroizing anything older bounds
+//! how much key material survives an enclave compromise that only recovers the in-memory window:
+//! a pruned epoch's secret is gone from it for good.
+//!
+//! This is *not* forward secrecy against compromise of the master secret itself: every epoch's
+//! secret is a pure function of the master secret and a small, enumerable epoch number, so
+//! whoever holds the master secret can always recompute any epoch, pruned or not. The window only
+//! protects against an attacker who obtains a running enclave's retained secrets without also
+//! obtaining the master secret.
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use oasis_core_keymanager_api_common::MasterSecret;
+use oasis_core_runtime::consensus::beacon::EpochTime;
+
+type Secret = Zeroizing<[u8; 32]>;
+
+/// A rolling window retaining only the last `N` epochs' independently-derived secrets, pruning
+/// (and zeroizing) anything older on demand. This bounds how much ephemeral key material an
+/// enclave compromise limited to its live retained secrets can recover -- it is not forward
+/// secrecy against compromise of the master secret itself, since every retained epoch is still a
+/// pure function of that master secret (see the module doc).
+pub struct Window {
+    /// Number of most recent epochs retained.
+    size: u64,
+    /// How far into the future (relative to the latest verified consensus epoch) a request may
+    /// name before being rejected.
+    max_future_epochs: u64,
+    /// Retained secrets, oldest first. May be sparse -- each epoch is keyed independently of the
+    /// others, so there is no need to materialize ones that were never actually requested.
+    secrets: BTreeMap<EpochTime, Secret>,
+}
+
+impl Window {
+    pub fn new(size: u64, max_future_epochs: u64) -> Self {
+        Self {
+            size: size.max(1),
+            max_future_epochs,
+            secrets: BTreeMap::new(),
+        }
+    }
+
+    /// Ensure the secret for `target` is available, deriving it if it is not yet retained.
+    /// Rejects `target` if it has already rotated out of the window, or if it is further in the
+    /// future than `max_future_epochs` past `verified_epoch` (the latest epoch known from the
+    /// verified consensus state).
+    pub fn ensure(
+        &mut self,
+        target: EpochTime,
+        verified_epoch: EpochTime,
+        master_secret: &MasterSecret,
+    ) -> Result<()> {
+        if target > verified_epoch + self.max_future_epochs {
+            return Err(anyhow!(
+                "ephemeral: epoch {} is too far in the future (verified epoch {})",
+                target,
+                verified_epoch
+            ));
+        }
+
+        if self.secrets.contains_key(&target) {
+            return Ok(());
+        }
+
+        if let Some(&highest) = self.secrets.keys().next_back() {
+            // Each epoch is keyed independently of the others (see the module doc), so any epoch
+            // still inside the retained `size`-wide window is re-derivable on demand regardless
+            // of whether it is above or below the highest epoch served so far -- only an epoch
+            // that has actually rotated out of the window is gone for good.
+            let lower_bound = highest.saturating_sub(self.size - 1);
+            if target < lower_bound {
+                return Err(anyhow!(
+                    "ephemeral: epoch {} has been pruned from the retained window",
+                    target
+                ));
+            }
+        }
+
+        self.insert(target, derive_epoch_secret(master_secret, target));
+        Ok(())
+    }
+
+    /// Return the secret for `target`, which must have already been made available via `ensure`.
+    pub fn get(&self, target: EpochTime) -> Result<&[u8; 32]> {
+        self.secrets
+            .get(&target)
+            .map(|s| &**s)
+            .ok_or_else(|| anyhow!("ephemeral: epoch {} not available", target))
+    }
+
+    fn insert(&mut self, epoch: EpochTime, secret: Secret) {
+        self.secrets.insert(epoch, secret);
+        let highest = *self.secrets.keys().next_back().unwrap();
+        let lower_bound = highest.saturating_sub(self.size - 1);
+        while let Some(&oldest) = self.secrets.keys().next() {
+            if oldest >= lower_bound {
+                break;
+            }
+            // `Secret` zeroizes on drop, so the pruned epoch's key material is actually wiped,
+            // not merely unreferenced.
+            self.secrets.remove(&oldest);
+        }
+    }
+}
+
+/// Key a single epoch's secret directly off the master secret: `H(domain || master || epoch)`,
+/// independent of every other epoch and of request history.
+fn derive_epoch_secret(master_secret: &MasterSecret, epoch: EpochTime) -> Secret {
+    let mut hasher = Sha256::new();
+    hasher.update(b"oasis-core/keymanager-lib: ephemeral secret");
+    hasher.update(&master_secret.0);
+    hasher.update(epoch.to_le_bytes());
+    Zeroizing::new(hasher.finalize().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn master_secret() -> MasterSecret {
+        let mut ms = MasterSecret::default();
+        ms.0.copy_from_slice(&[7u8; 32]);
+        ms
+    }
+
+    #[test]
+    fn test_derivation_is_deterministic_within_an_epoch() {
+        let ms = master_secret();
+        let mut window = Window::new(4, 1);
+        window.ensure(10, 10, &ms).unwrap();
+        let first = *window.get(10).unwrap();
+
+        // Re-requesting the same (already cached) epoch must not change its secret.
+        window.ensure(10, 10, &ms).unwrap();
+        let second = *window.get(10).unwrap();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_derivation_is_independent_of_request_order() {
+        let ms = master_secret();
+
+        // Two independent replicas (or a single enclave before and after a restart) whose first
+        // request lands on a different epoch must still agree on any epoch they both later serve.
+        let mut first_requester = Window::new(4, 0);
+        first_requester.ensure(100, 105, &ms).unwrap();
+        first_requester.ensure(105, 105, &ms).unwrap();
+
+        let mut second_requester = Window::new(4, 0);
+        second_requester.ensure(102, 105, &ms).unwrap();
+        second_requester.ensure(105, 105, &ms).unwrap();
+
+        assert_eq!(
+            *first_requester.get(105).unwrap(),
+            *second_requester.get(105).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_serves_older_epoch_still_within_the_retained_window() {
+        let ms = master_secret();
+        let mut window = Window::new(4, 0);
+
+        // Request epoch 105 first, then an older epoch that is still within the size-4 window
+        // (102..=105): this must succeed, not be treated as "pruned", since retention is keyed
+        // by window membership, not by whether requests have so far arrived in increasing order.
+        window.ensure(105, 105, &ms).unwrap();
+        window.ensure(104, 105, &ms).unwrap();
+
+        assert_eq!(*window.get(104).unwrap(), *derive_epoch_secret(&ms, 104));
+    }
+
+    #[test]
+    fn test_rejects_pruned_epoch() {
+        let ms = master_secret();
+        let mut window = Window::new(2, 5);
+        window.ensure(1, 1, &ms).unwrap();
+        window.ensure(2, 2, &ms).unwrap();
+        window.ensure(3, 3, &ms).unwrap();
+
+        // Epoch 1 has rotated out of the size-2 window.
+        assert!(window.ensure(1, 3, &ms).is_err());
+        assert!(window.get(1).is_err());
+    }
+
+    #[test]
+    fn test_rejects_too_far_future_epoch() {
+        let ms = master_secret();
+        let mut window = Window::new(4, 2);
+        assert!(window.ensure(10, 5, &ms).is_err());
+        assert!(window.ensure(7, 5, &ms).is_ok());
+    }
+
+    #[test]
+    fn test_rotating_past_window_drops_old_secret() {
+        let ms = master_secret();
+        let mut window = Window::new(2, 10);
+        window.ensure(1, 1, &ms).unwrap();
+        window.ensure(2, 2, &ms).unwrap();
+        assert!(window.get(1).is_ok());
+
+        window.ensure(3, 3, &ms).unwrap();
+        // Epoch 1's secret is no longer retained anywhere -- it was zeroized and removed rather
+        // than just shadowed.
+        assert!(window.get(1).is_err());
+        assert_eq!(window.secrets.len(), 2);
+    }
+}
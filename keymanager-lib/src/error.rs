@@ -0,0 +1,41 @@
+//! Keymanager-lib's own error type, surfaced over EnclaveRPC with a stable per-variant code --
+//! mirrors the convention `consensus::verifier::Error` uses for the same purpose, so that a
+//! distinct failure like an ACL denial doesn't collapse into an indistinguishable internal error
+//! on the wire.
+use thiserror::Error as ThisError;
+
+use oasis_core_runtime::types::Error as RpcError;
+
+#[derive(Debug, ThisError)]
+pub enum Error {
+    #[error("access denied")]
+    AccessDenied,
+
+    #[error("{0}")]
+    Internal(#[source] anyhow::Error),
+}
+
+impl Error {
+    fn code(&self) -> u32 {
+        match self {
+            Error::AccessDenied => 1,
+            Error::Internal(_) => 2,
+        }
+    }
+}
+
+impl From<anyhow::Error> for Error {
+    fn from(err: anyhow::Error) -> Self {
+        Error::Internal(err)
+    }
+}
+
+impl From<Error> for RpcError {
+    fn from(e: Error) -> Self {
+        Self {
+            module: "keymanager".to_string(),
+            code: e.code(),
+            message: e.to_string(),
+        }
+    }
+}
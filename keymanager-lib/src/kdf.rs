@@ -0,0 +1,409 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{anyhow, Result};
+use lazy_static::lazy_static;
+use sha2::{Digest, Sha256};
+
+use oasis_core_keymanager_api_common::*;
+use oasis_core_runtime::{
+    common::{crypto::signature::PublicKey, namespace::Namespace},
+    consensus::beacon::EpochTime,
+    enclave_rpc::Context as RpcContext,
+};
+
+use crate::{
+    acl::AclStore,
+    client::{EnclaveRpcPeerClient, PeerClient},
+    context::Context,
+    dkg,
+    ephemeral,
+    error::Error as KeymanagerError,
+    policy::Policy,
+};
+
+lazy_static! {
+    static ref KDF: Kdf = Kdf::new();
+}
+
+/// State for a DKG session this enclave is participating in: either the one that generated the
+/// master secret this node already holds, or one it is still trying to join.
+struct Session {
+    /// Our own random polynomial's contribution, evaluated for every participant (including
+    /// ourselves). Kept around so that any peer -- not just the one we happen to be recovering
+    /// alongside -- can pull their share of it from us via `get_master_secret_share`.
+    own_contribution: dkg::Contribution,
+}
+
+struct Inner {
+    /// The reconstructed master secret, once known.
+    master_secret: Option<MasterSecret>,
+    checksum: Option<Vec<u8>>,
+    dkg_sessions: HashMap<dkg::SessionId, Session>,
+    /// Rolling window of derived epoch-scoped ephemeral secrets.
+    ephemeral_secrets: ephemeral::Window,
+    /// Latest epoch known from verified consensus state, used to bound how far into the future
+    /// an ephemeral key request may name. `None` until the first `notify_epoch` call.
+    verified_epoch: Option<EpochTime>,
+}
+
+/// How far past the latest verified epoch a request may reach before being rejected as
+/// not-yet-determined.
+const MAX_FUTURE_EPOCHS: u64 = 1;
+
+/// Key derivation function state: holds the master secret (once generated or recovered) and
+/// derives per-runtime, per-epoch keys from it. The master secret is never materialized by a
+/// single enclave acting alone -- see `dkg` for the threshold generation protocol it is built on.
+pub struct Kdf {
+    inner: std::sync::RwLock<Inner>,
+}
+
+impl Kdf {
+    fn new() -> Self {
+        Self {
+            inner: std::sync::RwLock::new(Inner {
+                master_secret: None,
+                checksum: None,
+                dkg_sessions: HashMap::new(),
+                ephemeral_secrets: ephemeral::Window::new(1, MAX_FUTURE_EPOCHS),
+                verified_epoch: None,
+            }),
+        }
+    }
+
+    /// Return the global Kdf instance.
+    pub fn global<'a>() -> &'a Kdf {
+        &KDF
+    }
+
+    /// Initialize the Kdf: if a master secret is already held, this is a no-op beyond
+    /// recomputing the signed response. Otherwise, run or join the distributed key generation
+    /// session derived from `req`'s policy checksum, deriving the master secret without ever
+    /// holding it all in one enclave until `req.threshold` of `req.participants` combine.
+    pub fn init(
+        &self,
+        req: &InitRequest,
+        ctx: &mut RpcContext,
+        policy_checksum: Vec<u8>,
+    ) -> Result<SignedInitResponse> {
+        {
+            let inner = self.inner.read().unwrap();
+            if inner.master_secret.is_some() {
+                return self.sign_init_response(&inner, policy_checksum);
+            }
+        }
+
+        if !req.may_generate {
+            return Err(anyhow!("kdf: no master secret and generation not allowed"));
+        }
+        if req.participants.len() < req.threshold as usize {
+            return Err(anyhow!(
+                "kdf: fewer participants ({}) than threshold ({})",
+                req.participants.len(),
+                req.threshold
+            ));
+        }
+
+        let rpc_ctx = ctx.runtime.downcast_ref::<Context>().unwrap();
+        let session_id = derive_session_id(&rpc_ctx.runtime_id, &policy_checksum);
+        let client = EnclaveRpcPeerClient::new(rpc_ctx.protocol.clone());
+        let own_node_id = rpc_ctx.protocol.get_node_id();
+
+        self.run_dkg(
+            session_id,
+            req.threshold,
+            &req.participants,
+            own_node_id,
+            &client,
+        )?;
+
+        let mut inner = self.inner.write().unwrap();
+        inner.ephemeral_secrets = ephemeral::Window::new(req.ephemeral_secret_window, MAX_FUTURE_EPOCHS);
+        self.sign_init_response(&inner, policy_checksum)
+    }
+
+    /// Record the latest epoch known from verified consensus state. Called by the node key
+    /// manager component whenever its consensus light client advances, so that ephemeral key
+    /// requests can be bounded against a trustworthy notion of "now".
+    pub fn notify_epoch(&self, epoch: EpochTime) {
+        self.inner.write().unwrap().verified_epoch = Some(epoch);
+    }
+
+    /// Run (or join) the `t`-of-`n` DKG session `session_id` among `participants` (of which
+    /// `own_node_id` is one): contribute our own polynomial, then reconstruct `threshold`
+    /// participants' complete combined shares -- our own plus `threshold - 1` peers' -- and
+    /// recover the master secret from them via Lagrange interpolation.
+    fn run_dkg(
+        &self,
+        session_id: dkg::SessionId,
+        threshold: u32,
+        participants: &[PublicKey],
+        own_node_id: PublicKey,
+        client: &dyn PeerClient,
+    ) -> Result<()> {
+        // Index assignment is the participant's position in the order `participants` was given
+        // in (expected to be registry-sorted by node ID), so every enclave derives the same
+        // indices independently without a coordinator.
+        let indices: Vec<dkg::ParticipantIndex> = (1..=participants.len() as u32).collect();
+        let own_index = participants
+            .iter()
+            .position(|id| *id == own_node_id)
+            .map(|pos| indices[pos])
+            .ok_or_else(|| anyhow!("kdf: own node is not a participant in this dkg session"))?;
+
+        // A retry of the same session reuses the contribution peers may already have fetched.
+        let own_contribution = self
+            .inner
+            .write()
+            .unwrap()
+            .dkg_sessions
+            .entry(session_id)
+            .or_insert_with(|| Session {
+                own_contribution: dkg::contribute(threshold, &indices),
+            })
+            .own_contribution
+            .clone();
+
+        // Our own combined share is always the first point we recover.
+        let mut combined_shares = BTreeMap::new();
+        match collect_combined_share(
+            session_id,
+            own_index,
+            own_index,
+            &own_contribution,
+            participants,
+            &indices,
+            client,
+        ) {
+            Ok(share) => {
+                combined_shares.insert(own_index, share);
+            }
+            Err(_) => {
+                // Not every peer has joined the session yet; stay uninitialized until a later
+                // `init_kdf` call (triggered by a retry) succeeds.
+                return Ok(());
+            }
+        }
+
+        // Recovering the joint secret needs `threshold` *different* participants' combined
+        // shares, Lagrange-interpolated at 0 -- our own combined share is only one point. Collect
+        // each the same way: fetch every participant's raw evaluation at that point and sum them.
+        for &peer_index in &indices {
+            if combined_shares.len() >= threshold as usize {
+                break;
+            }
+            if peer_index == own_index {
+                continue;
+            }
+            if let Ok(share) = collect_combined_share(
+                session_id,
+                peer_index,
+                own_index,
+                &own_contribution,
+                participants,
+                &indices,
+                client,
+            ) {
+                combined_shares.insert(peer_index, share);
+            }
+        }
+
+        if combined_shares.len() < threshold as usize {
+            // Not enough peers are reachable yet; stay uninitialized until a later retry.
+            return Ok(());
+        }
+
+        let secret_scalar = dkg::recover(&combined_shares, threshold)?;
+        let mut master_secret = MasterSecret::default();
+        master_secret.0.copy_from_slice(secret_scalar.as_bytes());
+        let checksum = compute_checksum(&master_secret);
+
+        let mut inner = self.inner.write().unwrap();
+        inner.master_secret = Some(master_secret);
+        inner.checksum = Some(checksum);
+        Ok(())
+    }
+
+    /// Derive (or fetch the already-derived) runtime signing/encryption key pair for `req`,
+    /// after checking that the calling runtime is authorized to request this key id.
+    pub fn get_or_create_keys(&self, ctx: &mut RpcContext, req: &KeyRequest) -> Result<KeyPair> {
+        let caller_runtime_id = caller_runtime_id(ctx)?;
+        let rpc_ctx = ctx.runtime.downcast_ref::<Context>().unwrap();
+        let state = rpc_ctx.consensus_verifier.latest_state()?;
+        let height = rpc_ctx.consensus_verifier.latest_height()?;
+        let policy_rules = Policy::global().acl_rules();
+        let allowed = AclStore::global().is_allowed(
+            &state,
+            height,
+            &caller_runtime_id,
+            req.key_pair_id.as_ref(),
+            &policy_rules,
+        )?;
+        if !allowed {
+            return Err(KeymanagerError::AccessDenied.into());
+        }
+
+        let inner = self.inner.read().unwrap();
+        let master_secret = inner
+            .master_secret
+            .as_ref()
+            .ok_or_else(|| anyhow!("kdf: not yet initialized"))?;
+        Ok(derive_key_pair(master_secret, &req.key_pair_id))
+    }
+
+    /// Return only the public half of the key pair that `get_or_create_keys` would derive.
+    pub fn get_public_key(
+        &self,
+        ctx: &mut RpcContext,
+        req: &KeyRequest,
+    ) -> Result<SignedPublicKey> {
+        let pair = self.get_or_create_keys(ctx, req)?;
+        Ok(sign_public_key(pair.input_keypair.pk))
+    }
+
+    /// Derive the ephemeral key pair for `req.key_pair_id` scoped to `req.epoch`, rejecting the
+    /// request if that epoch has rotated out of the retained window or lies too far in the
+    /// future relative to the latest verified consensus epoch.
+    pub fn get_or_create_ephemeral_keys(
+        &self,
+        _ctx: &mut RpcContext,
+        req: &EphemeralKeyRequest,
+    ) -> Result<KeyPair> {
+        let mut inner = self.inner.write().unwrap();
+        let verified_epoch = inner
+            .verified_epoch
+            .ok_or_else(|| anyhow!("kdf: no verified epoch known yet"))?;
+        let master_secret = inner
+            .master_secret
+            .clone()
+            .ok_or_else(|| anyhow!("kdf: not yet initialized"))?;
+
+        inner
+            .ephemeral_secrets
+            .ensure(req.epoch, verified_epoch, &master_secret)?;
+        let secret = *inner.ephemeral_secrets.get(req.epoch)?;
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"oasis-core/keymanager-lib: derive ephemeral key pair");
+        hasher.update(secret);
+        hasher.update(req.key_pair_id.as_ref());
+        Ok(KeyPair::from_seed(hasher.finalize().into()))
+    }
+
+    /// Return only the public half of the key pair that `get_or_create_ephemeral_keys` would
+    /// derive.
+    pub fn get_public_ephemeral_key(
+        &self,
+        ctx: &mut RpcContext,
+        req: &EphemeralKeyRequest,
+    ) -> Result<SignedPublicKey> {
+        let pair = self.get_or_create_ephemeral_keys(ctx, req)?;
+        Ok(sign_public_key(pair.input_keypair.pk))
+    }
+
+    /// Serve our own polynomial's evaluation at `recipient_index`, plus the commitment needed to
+    /// verify it, to a requesting peer -- the `get_master_secret_share` RPC handler. Errors if we
+    /// have not joined that session yet, or if `recipient_index` is not one of its participants.
+    pub fn get_master_secret_share(
+        &self,
+        session_id: dkg::SessionId,
+        recipient_index: dkg::ParticipantIndex,
+    ) -> Result<(dkg::Commitment, dkg::Share)> {
+        let inner = self.inner.read().unwrap();
+        let session = inner
+            .dkg_sessions
+            .get(&session_id)
+            .ok_or_else(|| anyhow!("kdf: unknown dkg session"))?;
+        let share = *session
+            .own_contribution
+            .shares
+            .get(&recipient_index)
+            .ok_or_else(|| anyhow!("kdf: recipient {} is not a dkg participant", recipient_index))?;
+        Ok((session.own_contribution.commitment.clone(), share))
+    }
+
+    fn sign_init_response(
+        &self,
+        inner: &Inner,
+        policy_checksum: Vec<u8>,
+    ) -> Result<SignedInitResponse> {
+        let checksum = inner
+            .checksum
+            .clone()
+            .ok_or_else(|| anyhow!("kdf: master secret not yet available"))?;
+        Ok(sign_init_response(InitResponse {
+            is_secure: true,
+            checksum,
+            policy_checksum,
+        }))
+    }
+}
+
+/// The runtime ID endorsing the RAK that established the current secure RPC session, i.e. the
+/// runtime on whose behalf this request is being made.
+fn caller_runtime_id(ctx: &RpcContext) -> Result<Namespace> {
+    ctx.session_info
+        .as_ref()
+        .and_then(|info| info.endorsed_runtime_id)
+        .ok_or_else(|| anyhow!("acl: caller runtime is not endorsed by an attested RAK"))
+}
+
+fn derive_key_pair(master_secret: &MasterSecret, key_pair_id: &KeyPairId) -> KeyPair {
+    let mut hasher = Sha256::new();
+    hasher.update(b"oasis-core/keymanager-lib: derive key pair");
+    hasher.update(&master_secret.0);
+    hasher.update(key_pair_id.as_ref());
+    KeyPair::from_seed(hasher.finalize().into())
+}
+
+fn derive_session_id(runtime_id: &Namespace, policy_checksum: &[u8]) -> dkg::SessionId {
+    let mut hasher = Sha256::new();
+    hasher.update(b"oasis-core/keymanager-lib: dkg session");
+    hasher.update(runtime_id.as_ref());
+    hasher.update(policy_checksum);
+    hasher.finalize().into()
+}
+
+/// Reconstruct the joint polynomial's combined evaluation at `target_index` -- a single point
+/// suitable for Lagrange interpolation -- by summing `own_contribution`'s own evaluation there
+/// with every other participant's evaluation at the same point, fetched and verified against
+/// their commitment over the wire.
+///
+/// This is the one exchange model the whole DKG session uses, whether `target_index` is our own
+/// index (building our own combined share) or a peer's (reconstructing a point we
+/// need for recovery): a combined share is only ever a local sum of individually verified raw
+/// shares, never something fetched pre-combined from a peer. It requires a response from every
+/// other participant; the combined shares fed into one recovery must all be sums over the same
+/// set of contributions; or Lagrange interpolation across them would not reconstruct the joint
+/// polynomial.
+fn collect_combined_share(
+    session_id: dkg::SessionId,
+    target_index: dkg::ParticipantIndex,
+    own_index: dkg::ParticipantIndex,
+    own_contribution: &dkg::Contribution,
+    participants: &[PublicKey],
+    indices: &[dkg::ParticipantIndex],
+    client: &dyn PeerClient,
+) -> Result<dkg::Share> {
+    let mut accumulator = dkg::Accumulator::new(target_index);
+    accumulator.accept(
+        &own_contribution.commitment,
+        &own_contribution.shares[&target_index],
+    )?;
+
+    for (peer_node_id, &peer_index) in participants.iter().zip(indices) {
+        if peer_index == own_index {
+            continue;
+        }
+        let (commitment, share) =
+            client.get_master_secret_share(peer_node_id, session_id, target_index)?;
+        accumulator.accept(&commitment, &share)?;
+    }
+
+    if accumulator.contributions_received() != participants.len() {
+        return Err(anyhow!(
+            "kdf: incomplete contribution set for index {}",
+            target_index
+        ));
+    }
+    Ok(accumulator.combined_share())
+}
@@ -46,9 +46,27 @@ pub fn new_keymanager(signers: TrustedPolicySigners) -> Box<dyn Initializer> {
         state.rpc_dispatcher.add_method(
             RpcMethod::new(
                 RpcMethodDescriptor {
-                    name: METHOD_REPLICATE_MASTER_SECRET.to_string(),
+                    name: METHOD_GET_MASTER_SECRET_SHARE.to_string(),
                 },
-                methods::replicate_master_secret,
+                methods::get_master_secret_share,
+            ),
+            false,
+        );
+        state.rpc_dispatcher.add_method(
+            RpcMethod::new(
+                RpcMethodDescriptor {
+                    name: METHOD_GET_OR_CREATE_EPHEMERAL_KEYS.to_string(),
+                },
+                methods::get_or_create_ephemeral_keys,
+            ),
+            false,
+        );
+        state.rpc_dispatcher.add_method(
+            RpcMethod::new(
+                RpcMethodDescriptor {
+                    name: METHOD_GET_PUBLIC_EPHEMERAL_KEY.to_string(),
+                },
+                methods::get_public_ephemeral_key,
             ),
             false,
         );
@@ -63,15 +81,26 @@ pub fn new_keymanager(signers: TrustedPolicySigners) -> Box<dyn Initializer> {
             ),
             true,
         );
+        state.rpc_dispatcher.add_method(
+            RpcMethod::new(
+                RpcMethodDescriptor {
+                    name: LOCAL_METHOD_NOTIFY_EPOCH.to_string(),
+                },
+                methods::notify_epoch,
+            ),
+            true,
+        );
 
         let runtime_id = state.protocol.get_runtime_id();
         let protocol = state.protocol.clone(); // Shut up the borrow checker.
+        let consensus_verifier = state.consensus_verifier.clone();
         state
             .rpc_dispatcher
             .set_context_initializer(move |ctx: &mut RpcContext| {
                 ctx.runtime = Box::new(context::Context {
                     runtime_id,
                     protocol: protocol.clone(),
+                    consensus_verifier: consensus_verifier.clone(),
                 })
             });
 
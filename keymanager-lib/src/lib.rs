@@ -0,0 +1,12 @@
+extern crate lazy_static;
+
+mod acl;
+mod client;
+mod context;
+mod dkg;
+mod ephemeral;
+mod error;
+pub mod keymanager;
+mod kdf;
+mod methods;
+mod policy;
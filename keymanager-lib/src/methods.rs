@@ -0,0 +1,55 @@
+//! RPC method handlers exposed via EnclaveRPC to remote clients.
+use anyhow::Result;
+
+use oasis_core_keymanager_api_common::*;
+use oasis_core_runtime::{consensus::beacon::EpochTime, enclave_rpc::Context as RpcContext};
+
+use crate::{
+    client::{ShareRequest, ShareResponse},
+    kdf::Kdf,
+};
+
+pub fn get_or_create_keys(ctx: &mut RpcContext, req: &KeyRequest) -> Result<KeyPair> {
+    Kdf::global().get_or_create_keys(ctx, req)
+}
+
+pub fn get_public_key(ctx: &mut RpcContext, req: &KeyRequest) -> Result<SignedPublicKey> {
+    Kdf::global().get_public_key(ctx, req)
+}
+
+pub fn get_or_create_ephemeral_keys(
+    ctx: &mut RpcContext,
+    req: &EphemeralKeyRequest,
+) -> Result<KeyPair> {
+    Kdf::global().get_or_create_ephemeral_keys(ctx, req)
+}
+
+pub fn get_public_ephemeral_key(
+    ctx: &mut RpcContext,
+    req: &EphemeralKeyRequest,
+) -> Result<SignedPublicKey> {
+    Kdf::global().get_public_ephemeral_key(ctx, req)
+}
+
+/// Notify the Kdf of the latest epoch known from verified consensus state, so it can bound
+/// ephemeral key requests. Local-only: invoked by the node key manager component, not remote
+/// clients.
+pub fn notify_epoch(_ctx: &mut RpcContext, epoch: &EpochTime) -> Result<()> {
+    Kdf::global().notify_epoch(*epoch);
+    Ok(())
+}
+
+/// Serve our own polynomial's evaluation at `req.recipient_index` (plus the commitment needed to
+/// verify it) for the requested DKG session to a peer enclave that is running or joining the same
+/// session -- the replacement for the old wholesale `replicate_master_secret`.
+pub fn get_master_secret_share(
+    _ctx: &mut RpcContext,
+    req: &ShareRequest,
+) -> Result<ShareResponse> {
+    let (commitment, share) =
+        Kdf::global().get_master_secret_share(req.session_id, req.recipient_index)?;
+    Ok(ShareResponse {
+        commitment: commitment.to_bytes(),
+        share: share.to_bytes(),
+    })
+}
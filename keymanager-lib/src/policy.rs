@@ -0,0 +1,62 @@
+use std::sync::RwLock;
+
+use anyhow::Result;
+use lazy_static::lazy_static;
+
+use oasis_core_keymanager_api_common::*;
+use oasis_core_runtime::enclave_rpc::Context as RpcContext;
+
+lazy_static! {
+    static ref POLICY: Policy = Policy::new();
+}
+
+/// Holds the currently active, signature-verified key manager policy.
+pub struct Policy {
+    inner: RwLock<Option<SignedPolicySGX>>,
+}
+
+impl Policy {
+    fn new() -> Self {
+        Self {
+            inner: RwLock::new(None),
+        }
+    }
+
+    /// Return the global policy instance.
+    pub fn global<'a>() -> &'a Policy {
+        &POLICY
+    }
+
+    /// Verify and install `signed_policy` as the active policy, returning its checksum.
+    pub fn init(
+        &self,
+        _ctx: &mut RpcContext,
+        signed_policy: &Option<SignedPolicySGX>,
+    ) -> Result<Vec<u8>> {
+        let checksum = match signed_policy {
+            Some(signed_policy) => {
+                signed_policy.verify(&trusted_policy_signers())?;
+                signed_policy.checksum()
+            }
+            None => Hash::default().as_ref().to_vec(),
+        };
+
+        *self.inner.write().unwrap() = signed_policy.clone();
+        Ok(checksum)
+    }
+
+    /// Return the currently active policy document, if any has been installed.
+    pub fn get(&self) -> Option<SignedPolicySGX> {
+        self.inner.read().unwrap().clone()
+    }
+
+    /// Return the ACL rules declared in the policy document's optional ACL section, if any.
+    pub fn acl_rules(&self) -> Vec<AclRule> {
+        self.inner
+            .read()
+            .unwrap()
+            .as_ref()
+            .map(|signed_policy| signed_policy.policy.acl.clone())
+            .unwrap_or_default()
+    }
+}
@@ -20,23 +20,61 @@ pub enum Error {
     #[error("builder: {0}")]
     Builder(#[source] anyhow::Error),
 
-    #[error("verification: {0}")]
-    VerificationFailed(#[source] anyhow::Error),
+    #[error("verification: {source}")]
+    VerificationFailed {
+        /// Consensus height at which the chain of trust broke, if verification was anchored to
+        /// a specific height (e.g. during skipping verification).
+        height: Option<u64>,
+        #[source]
+        source: anyhow::Error,
+    },
 
     #[error("trust root loading failed")]
     TrustRootLoadingFailed,
 
     #[error("internal consensus verifier error")]
     Internal,
+
+    #[error("consensus state freshness expired")]
+    FreshnessExpired,
+
+    #[error("consensus state temporarily unavailable: {0}")]
+    StateUnavailable(#[source] anyhow::Error),
 }
 
 impl Error {
     fn code(&self) -> u32 {
         match self {
             Error::Builder(_) => 1,
-            Error::VerificationFailed(_) => 2,
+            Error::VerificationFailed { .. } => 2,
             Error::TrustRootLoadingFailed => 3,
             Error::Internal => 4,
+            Error::FreshnessExpired => 5,
+            Error::StateUnavailable(_) => 6,
+        }
+    }
+
+    /// Whether this error reflects a momentary condition (stale state, consensus layer
+    /// temporarily unreachable) that a `sync` may resolve, as opposed to a genuine cryptographic
+    /// verification failure which is never retryable.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Error::FreshnessExpired | Error::StateUnavailable(_))
+    }
+
+    /// Construct a `VerificationFailed` error not anchored to any particular height.
+    fn verification_failed(source: anyhow::Error) -> Self {
+        Error::VerificationFailed {
+            height: None,
+            source,
+        }
+    }
+
+    /// Construct a `VerificationFailed` error anchored to the height at which the chain of trust
+    /// broke, e.g. the point where skipping verification could not establish sufficient trust.
+    fn verification_failed_at(height: u64, source: anyhow::Error) -> Self {
+        Error::VerificationFailed {
+            height: Some(height),
+            source,
         }
     }
 }
@@ -51,16 +89,46 @@ impl From<Error> for types::Error {
     }
 }
 
+/// Run `attempt`, and if it fails with a transient error, `sync` to `height` and run it once
+/// more. Shared by `Verifier::verify`/`verify_for_query`'s default bodies so the retry policy is
+/// defined -- and tested -- in exactly one place, generic over the actual result type so it can
+/// be exercised without a real consensus state.
+fn with_transient_retry<T>(
+    height: u64,
+    sync: impl FnOnce(u64) -> Result<(), Error>,
+    mut attempt: impl FnMut() -> Result<T, Error>,
+) -> Result<T, Error> {
+    match attempt() {
+        Err(err) if err.is_transient() => {
+            sync(height)?;
+            attempt()
+        }
+        result => result,
+    }
+}
+
 /// Verifier is the consensus layer state verifier trait.
 pub trait Verifier: Send + Sync {
     /// Synchronize the verifier state up to including the passed consensus height.
     fn sync(&self, height: u64) -> Result<(), Error>;
 
     /// Verify that the given runtime header is valid at the given consensus layer block and return
-    /// the consensus layer state accessor for that block.
+    /// the consensus layer state accessor for that block, without retrying on transient failures.
     ///
     /// This also verifies that the state is fresh.
-    fn verify(
+    fn verify_once(
+        &self,
+        consensus_block: LightBlock,
+        runtime_header: Header,
+        epoch: EpochTime,
+    ) -> Result<ConsensusState, Error>;
+
+    /// Verify that the given runtime header is valid at the given consensus layer block and return
+    /// the consensus layer state accessor for that block, without retrying on transient failures.
+    ///
+    /// This is a relaxed version of `verify_once` that should be used for verifying state in
+    /// queries.
+    fn verify_for_query_once(
         &self,
         consensus_block: LightBlock,
         runtime_header: Header,
@@ -70,14 +138,36 @@ pub trait Verifier: Send + Sync {
     /// Verify that the given runtime header is valid at the given consensus layer block and return
     /// the consensus layer state accessor for that block.
     ///
-    /// This is a relaxed version of the `verify` function that should be used for verifying state
-    /// in queries.
+    /// This also verifies that the state is fresh. A transient failure (e.g. state is stale, or
+    /// consensus is momentarily unreachable) triggers one `sync` to the block's height followed
+    /// by a single retry before giving up; a non-transient failure is returned immediately.
+    fn verify(
+        &self,
+        consensus_block: LightBlock,
+        runtime_header: Header,
+        epoch: EpochTime,
+    ) -> Result<ConsensusState, Error> {
+        with_transient_retry(
+            consensus_block.height,
+            |height| self.sync(height),
+            || self.verify_once(consensus_block.clone(), runtime_header.clone(), epoch),
+        )
+    }
+
+    /// Relaxed version of `verify` that should be used for verifying state in queries, with the
+    /// same transient-failure retry behavior.
     fn verify_for_query(
         &self,
         consensus_block: LightBlock,
         runtime_header: Header,
         epoch: EpochTime,
-    ) -> Result<ConsensusState, Error>;
+    ) -> Result<ConsensusState, Error> {
+        with_transient_retry(
+            consensus_block.height,
+            |height| self.sync(height),
+            || self.verify_for_query_once(consensus_block.clone(), runtime_header.clone(), epoch),
+        )
+    }
 
     /// Return the consensus layer state accessor for the given consensus layer block WITHOUT
     /// performing any verification. This method should only be used for operations that do not
@@ -105,6 +195,49 @@ pub trait Verifier: Send + Sync {
 
     /// Record the given (locally computed and thus verified) results header as trusted.
     fn trust(&self, header: &ComputeResultsHeader) -> Result<(), Error>;
+
+    /// Synchronize and verify up to and including `target_height`, without replaying every
+    /// intermediate height.
+    ///
+    /// Implements Tendermint-style bisection: starting from the latest trusted state, first
+    /// attempts a non-adjacent "skip" directly to `target_height`, accepting it if validators
+    /// of the trusted set that also signed the target's commit hold more than
+    /// `trust_root.trust_options`' trust level of the trusted voting power. If the target is adjacent to the trusted height,
+    /// the stricter rule applies instead: more than 2/3 of the *new* validator set must have
+    /// signed, and the trusted header's `next_validators_hash` must match the new validator set.
+    /// If neither holds, bisects at the midpoint and recurses on each half, up to
+    /// `trust_options.max_bisection_depth` times.
+    ///
+    /// Returns `Error::VerificationFailed` with the height at which the chain of trust broke if
+    /// no sequence of skips/bisections can reach `target_height`.
+    ///
+    /// A concrete implementation is expected to delegate the actual skip/bisect decision to
+    /// `verify_skipping`, implementing `SkipCheckSource` to supply the per-hop voting-power facts
+    /// from its own header/validator-set source, then fetch `state_at(target_height)` once
+    /// `verify_skipping` returns `Ok`.
+    fn verify_to(&self, target_height: u64) -> Result<ConsensusState, Error>;
+}
+
+/// Parameters controlling skipping (bisection) verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, cbor::Encode, cbor::Decode)]
+pub struct TrustOptions {
+    /// Numerator of the minimum fraction of trusted voting power that must overlap with signers
+    /// of a candidate header for a non-adjacent skip to be accepted.
+    pub trust_level_numerator: u64,
+    /// Denominator of the trust level fraction.
+    pub trust_level_denominator: u64,
+    /// Maximum bisection recursion depth before giving up.
+    pub max_bisection_depth: u16,
+}
+
+impl Default for TrustOptions {
+    fn default() -> Self {
+        Self {
+            trust_level_numerator: 1,
+            trust_level_denominator: 3,
+            max_bisection_depth: 20,
+        }
+    }
 }
 
 /// Consensus layer trust root.
@@ -116,6 +249,8 @@ pub struct TrustRoot {
     pub hash: String,
     /// Known runtime identifier.
     pub runtime_id: Namespace,
+    /// Parameters for skipping (bisection) verification from this trust root.
+    pub trust_options: TrustOptions,
 }
 
 /// Verify consensus layer state freshness based on our internal state.
@@ -134,22 +269,25 @@ pub fn verify_state_freshness(
     match node_id {
         // Node ID is cached, query the node and check for matching RAK.
         Some(node_id) => {
+            // A registry query failure here most often means our consensus light client hasn't
+            // synced far enough yet for this state to be queryable -- transient, and worth a
+            // `sync` and a single retry rather than failing verification outright.
             let node = registry_state
                 .node(Context::background(), node_id)
                 .map_err(|err| {
-                    Error::VerificationFailed(anyhow!(
+                    Error::StateUnavailable(anyhow!(
                         "failed to retrieve node from the registry: {}",
                         err
                     ))
                 })?;
             let node = node.ok_or_else(|| {
-                Error::VerificationFailed(anyhow!(
+                Error::verification_failed(anyhow!(
                     "own node ID '{}' not found in registry state",
                     node_id,
                 ))
             })?;
             if !node.has_tee(rak, &trust_root.runtime_id, version) {
-                return Err(Error::VerificationFailed(anyhow!(
+                return Err(Error::verification_failed(anyhow!(
                     "own RAK not found in registry state"
                 )));
             }
@@ -158,8 +296,10 @@ pub fn verify_state_freshness(
         }
         // Node ID not cached, need to scan all registry nodes.
         None => {
+            // Same reasoning as the cached-node-id branch above: a registry query failure is
+            // treated as transient state unavailability, not a fatal verification failure.
             let nodes = registry_state.nodes(Context::background()).map_err(|err| {
-                Error::VerificationFailed(anyhow!(
+                Error::StateUnavailable(anyhow!(
                     "failed to retrieve nodes from the registry: {}",
                     err
                 ))
@@ -172,7 +312,7 @@ pub fn verify_state_freshness(
                 }
             }
             if found_node.is_none() {
-                return Err(Error::VerificationFailed(anyhow!(
+                return Err(Error::verification_failed(anyhow!(
                     "own RAK not found in registry state",
                 )));
             }
@@ -182,3 +322,259 @@ pub fn verify_state_freshness(
         }
     }
 }
+
+/// The voting-power facts needed to decide whether a single hop from a trusted height to a
+/// candidate height can be accepted without bisecting further. A concrete `Verifier`
+/// implementation computes this by fetching both headers/validator sets and intersecting them.
+#[derive(Debug, Clone, Copy)]
+pub struct SkipCheck {
+    /// Whether the candidate height is exactly one block after the trusted height.
+    pub adjacent: bool,
+    /// Basic header checks (height, chain ID, monotonic time, ...) already passed.
+    pub header_valid: bool,
+    /// Voting power of the *trusted* validator set held by validators that also signed the
+    /// candidate header's commit. Only meaningful for non-adjacent skips.
+    pub trusted_overlap_power: u64,
+    /// Total voting power of the trusted validator set.
+    pub trusted_total_power: u64,
+    /// Voting power of the candidate's *new* validator set that signed its own commit. Only
+    /// meaningful for adjacent hops.
+    pub new_signing_power: u64,
+    /// Total voting power of the candidate's new validator set.
+    pub new_total_power: u64,
+    /// Whether the trusted header's `next_validators_hash` equals the new validator set's hash.
+    /// Only meaningful for adjacent hops.
+    pub next_validators_match: bool,
+}
+
+impl SkipCheck {
+    /// Whether this hop meets the acceptance rule for its kind (adjacent vs. skip).
+    fn is_trusted(&self, trust_options: &TrustOptions) -> bool {
+        if !self.header_valid {
+            return false;
+        }
+        if self.adjacent {
+            self.next_validators_match
+                && self.new_signing_power.saturating_mul(3) > self.new_total_power.saturating_mul(2)
+        } else {
+            self.trusted_overlap_power
+                .saturating_mul(trust_options.trust_level_denominator)
+                > self
+                    .trusted_total_power
+                    .saturating_mul(trust_options.trust_level_numerator)
+        }
+    }
+}
+
+/// Source of `SkipCheck` facts for an arbitrary `(trusted, candidate)` height pair, implemented
+/// by whatever has access to fetch and decode headers/validator sets at those heights.
+pub trait SkipCheckSource {
+    fn check(&self, trusted_height: u64, candidate_height: u64) -> Result<SkipCheck, Error>;
+}
+
+/// Verify that `target_height` is reachable from `trusted_height` via Tendermint-style skipping
+/// (bisection), per `trust_options`. See `Verifier::verify_to` for the acceptance rules.
+pub fn verify_skipping(
+    source: &dyn SkipCheckSource,
+    trusted_height: u64,
+    target_height: u64,
+    trust_options: &TrustOptions,
+) -> Result<(), Error> {
+    verify_skipping_depth(source, trusted_height, target_height, trust_options, 0)
+}
+
+fn verify_skipping_depth(
+    source: &dyn SkipCheckSource,
+    trusted_height: u64,
+    target_height: u64,
+    trust_options: &TrustOptions,
+    depth: u16,
+) -> Result<(), Error> {
+    if depth > trust_options.max_bisection_depth {
+        return Err(Error::verification_failed_at(
+            target_height,
+            anyhow!(
+                "exceeded maximum bisection depth ({})",
+                trust_options.max_bisection_depth
+            ),
+        ));
+    }
+
+    let check = source.check(trusted_height, target_height)?;
+    if check.is_trusted(trust_options) {
+        return Ok(());
+    }
+    if check.adjacent {
+        // There is no midpoint to bisect to between adjacent heights.
+        return Err(Error::verification_failed_at(
+            target_height,
+            anyhow!("adjacent header failed validator set verification"),
+        ));
+    }
+
+    let mid = trusted_height + (target_height - trusted_height) / 2;
+    verify_skipping_depth(source, trusted_height, mid, trust_options, depth + 1)?;
+    verify_skipping_depth(source, mid, target_height, trust_options, depth + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// A `SkipCheckSource` scripted with one `SkipCheck` per `(trusted, candidate)` height pair it
+    /// is expected to be asked about.
+    struct MockSource {
+        checks: HashMap<(u64, u64), SkipCheck>,
+    }
+
+    impl SkipCheckSource for MockSource {
+        fn check(&self, trusted_height: u64, candidate_height: u64) -> Result<SkipCheck, Error> {
+            self.checks
+                .get(&(trusted_height, candidate_height))
+                .copied()
+                .ok_or_else(|| {
+                    Error::verification_failed(anyhow!(
+                        "no mock check scripted for ({}, {})",
+                        trusted_height,
+                        candidate_height
+                    ))
+                })
+        }
+    }
+
+    fn non_adjacent(trusted_overlap_power: u64, trusted_total_power: u64) -> SkipCheck {
+        SkipCheck {
+            adjacent: false,
+            header_valid: true,
+            trusted_overlap_power,
+            trusted_total_power,
+            new_signing_power: 0,
+            new_total_power: 0,
+            next_validators_match: false,
+        }
+    }
+
+    fn adjacent(new_signing_power: u64, new_total_power: u64, next_validators_match: bool) -> SkipCheck {
+        SkipCheck {
+            adjacent: true,
+            header_valid: true,
+            trusted_overlap_power: 0,
+            trusted_total_power: 0,
+            new_signing_power,
+            new_total_power,
+            next_validators_match,
+        }
+    }
+
+    #[test]
+    fn test_accepted_non_adjacent_skip() {
+        let source = MockSource {
+            checks: HashMap::from([((1, 100), non_adjacent(80, 100))]),
+        };
+        verify_skipping(&source, 1, 100, &TrustOptions::default()).unwrap();
+    }
+
+    #[test]
+    fn test_rejected_skip_bisects_to_success() {
+        let source = MockSource {
+            checks: HashMap::from([
+                // The direct skip falls short of the trust level...
+                ((1, 100), non_adjacent(10, 100)),
+                // ...but each half clears it.
+                ((1, 50), non_adjacent(80, 100)),
+                ((50, 100), non_adjacent(80, 100)),
+            ]),
+        };
+        verify_skipping(&source, 1, 100, &TrustOptions::default()).unwrap();
+    }
+
+    #[test]
+    fn test_adjacent_next_validators_hash_mismatch_rejected() {
+        let source = MockSource {
+            checks: HashMap::from([((99, 100), adjacent(100, 100, false))]),
+        };
+        let err = verify_skipping(&source, 99, 100, &TrustOptions::default()).unwrap_err();
+        match err {
+            Error::VerificationFailed { height, .. } => assert_eq!(height, Some(100)),
+            other => panic!("expected VerificationFailed, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_transient_failure_triggers_one_sync_and_retry() {
+        let sync_calls = std::cell::Cell::new(0u32);
+        let attempts = std::cell::Cell::new(0u32);
+
+        let result = with_transient_retry(
+            42,
+            |height| {
+                assert_eq!(height, 42);
+                sync_calls.set(sync_calls.get() + 1);
+                Ok(())
+            },
+            || {
+                attempts.set(attempts.get() + 1);
+                if attempts.get() == 1 {
+                    Err(Error::StateUnavailable(anyhow!("light client not synced yet")))
+                } else {
+                    Ok(123)
+                }
+            },
+        );
+
+        assert_eq!(result.unwrap(), 123);
+        assert_eq!(sync_calls.get(), 1);
+        assert_eq!(attempts.get(), 2);
+    }
+
+    #[test]
+    fn test_non_transient_failure_does_not_retry() {
+        let sync_calls = std::cell::Cell::new(0u32);
+        let attempts = std::cell::Cell::new(0u32);
+
+        let result: Result<i32, Error> = with_transient_retry(
+            42,
+            |_height| {
+                sync_calls.set(sync_calls.get() + 1);
+                Ok(())
+            },
+            || {
+                attempts.set(attempts.get() + 1);
+                Err(Error::verification_failed(anyhow!("bad signature")))
+            },
+        );
+
+        assert!(result.is_err());
+        assert_eq!(sync_calls.get(), 0);
+        assert_eq!(attempts.get(), 1);
+    }
+
+    #[test]
+    fn test_max_bisection_depth_exhausted() {
+        /// A source whose checks never clear the trust level and are never adjacent until the
+        /// gap has narrowed to a single block, forcing bisection to recurse until depth runs out.
+        struct NeverTrustedSource;
+
+        impl SkipCheckSource for NeverTrustedSource {
+            fn check(&self, trusted_height: u64, candidate_height: u64) -> Result<SkipCheck, Error> {
+                Ok(non_adjacent(0, 100)).map(|mut check| {
+                    check.adjacent = candidate_height - trusted_height <= 1;
+                    check
+                })
+            }
+        }
+
+        let trust_options = TrustOptions {
+            max_bisection_depth: 2,
+            ..TrustOptions::default()
+        };
+        let err =
+            verify_skipping(&NeverTrustedSource, 0, 1000, &trust_options).unwrap_err();
+        match err {
+            Error::VerificationFailed { .. } => {}
+            other => panic!("expected VerificationFailed, got {:?}", other),
+        }
+    }
+}